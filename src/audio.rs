@@ -0,0 +1,91 @@
+/// In-process alert tones. `system_beep` used to shell out to `afplay`/
+/// `paplay`/`say` against hardcoded sound files that often don't exist;
+/// instead we synthesize the tone as PCM and play it through `rodio`,
+/// falling back to the terminal bell when no audio device is available.
+use std::time::Duration;
+
+use rodio::{OutputStream, Sink, Source};
+
+use crate::config::AudioConfig;
+
+const SAMPLE_RATE: u32 = 44_100;
+
+/// A synthesized sine-wave tone, sampled at 44.1kHz as `i16` PCM.
+struct ToneSource {
+    samples: std::vec::IntoIter<i16>,
+}
+
+impl ToneSource {
+    fn new(frequency_hz: f32, amplitude: f32, duration: Duration) -> Self {
+        let amplitude = amplitude.clamp(0.0, 1.0);
+        let sample_count = (SAMPLE_RATE as f64 * duration.as_secs_f64()) as usize;
+
+        let samples = (0..sample_count)
+            .map(|n| {
+                let t = n as f32 / SAMPLE_RATE as f32;
+                let value = (2.0 * std::f32::consts::PI * frequency_hz * t).sin();
+                (value * amplitude * i16::MAX as f32) as i16
+            })
+            .collect::<Vec<i16>>()
+            .into_iter();
+
+        Self { samples }
+    }
+}
+
+impl Iterator for ToneSource {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<i16> {
+        self.samples.next()
+    }
+}
+
+impl Source for ToneSource {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Plays an ascending two-note chime for the end of a work session.
+pub fn play_work_end(config: &AudioConfig) {
+    play_tones(config, &[config.frequency_hz, config.frequency_hz * 1.5]);
+}
+
+/// Plays a descending two-note chime for the end of a break.
+pub fn play_break_end(config: &AudioConfig) {
+    play_tones(config, &[config.frequency_hz * 1.5, config.frequency_hz]);
+}
+
+fn play_tones(config: &AudioConfig, frequencies: &[f32]) {
+    let Ok((_stream, handle)) = OutputStream::try_default() else {
+        terminal_bell();
+        return;
+    };
+    let Ok(sink) = Sink::try_new(&handle) else {
+        terminal_bell();
+        return;
+    };
+
+    for &frequency_hz in frequencies {
+        sink.append(ToneSource::new(frequency_hz, config.amplitude, config.tone_duration));
+    }
+    sink.sleep_until_end();
+}
+
+fn terminal_bell() {
+    print!("\x07");
+    std::io::Write::flush(&mut std::io::stdout()).unwrap();
+}
@@ -1,21 +1,148 @@
-/// Configuration module for Pomodoro settings
+/// Configuration module for Pomodoro settings.
+///
+/// Settings live in `config.toml` under the platform config directory
+/// (resolved via the `directories` crate) so the tool behaves correctly on
+/// macOS, Linux, and Windows without manually joining `HOME`/`.config`.
+use std::path::PathBuf;
 use std::time::Duration;
 
-#[derive(Debug)]
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
 pub struct Config {
+    pub hooks: Hooks,
+    #[serde(with = "duration_str")]
     pub work_duration: Duration,
-    pub break_duration: Duration,
+    #[serde(with = "duration_str")]
+    pub short_break: Duration,
+    #[serde(with = "duration_str")]
+    pub long_break: Duration,
     pub cycles: usize,
-    pub beep_sound: String,
+    pub long_break_interval: usize,
+    pub audio: AudioConfig,
+    pub notify: bool,
 }
 
-impl Config {
-    pub fn new() -> Self {
+impl Default for Config {
+    fn default() -> Self {
         Self {
-            work_duration: Duration::from_secs(1500), // 25 minutes
-            break_duration: Duration::from_secs(300), // 5 minutes
+            hooks: Hooks::default(),
+            work_duration: Duration::from_secs(25 * 60),
+            short_break: Duration::from_secs(5 * 60),
+            long_break: Duration::from_secs(15 * 60),
             cycles: 4,
-            beep_sound: "\x07".to_string(), // ASCII bell character
+            long_break_interval: 4,
+            audio: AudioConfig::default(),
+            notify: true,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Hooks {
+    pub work_start: Option<String>,
+    pub work_end: Option<String>,
+    pub break_start: Option<String>,
+    pub break_end: Option<String>,
+}
+
+/// Settings for the synthesized alert tone played at each transition.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct AudioConfig {
+    pub frequency_hz: f32,
+    pub amplitude: f32,
+    #[serde(with = "duration_str")]
+    pub tone_duration: Duration,
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        Self {
+            frequency_hz: 440.0,
+            amplitude: 0.8,
+            tone_duration: Duration::from_millis(200),
         }
     }
 }
+
+/// (De)serializes `Duration` as human-friendly strings like `25m`, `1h30m`
+/// or `200ms`, via the same grammar as the `--duration` CLI flag (see
+/// `crate::duration`) so config durations (e.g. `audio.tone_duration`)
+/// round-trip at sub-second precision without a second, drifting parser.
+mod duration_str {
+    use super::Duration;
+    use serde::{de::Error as _, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&crate::duration::format_compact(*duration))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        crate::duration::parse(&raw).map_err(D::Error::custom)
+    }
+}
+
+/// Loads the config from disk, writing out the defaults on first run (when
+/// the file is missing) and falling back to defaults (with a warning) if
+/// the file can't be read or parsed.
+pub fn load() -> Config {
+    let Some(path) = config_path() else {
+        eprintln!("Warning: could not determine config directory, using defaults");
+        return Config::default();
+    };
+
+    if !path.exists() {
+        let default_config = Config::default();
+        write(&path, &default_config);
+        return default_config;
+    }
+
+    let raw = match std::fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(_) => {
+            eprintln!("Warning: could not read config file, using defaults");
+            return Config::default();
+        }
+    };
+
+    match toml::from_str(&raw) {
+        Ok(config) => validate(config),
+        Err(err) => {
+            eprintln!("Warning: invalid config ({err}), using defaults");
+            Config::default()
+        }
+    }
+}
+
+/// Clamps fields that would otherwise let a syntactically valid but
+/// nonsensical config (e.g. `long_break_interval = 0`) crash the timer
+/// loop with a divide-by-zero.
+fn validate(mut config: Config) -> Config {
+    if config.cycles == 0 {
+        eprintln!("Warning: config `cycles` must be greater than 0, using default");
+        config.cycles = Config::default().cycles;
+    }
+    if config.long_break_interval == 0 {
+        eprintln!("Warning: config `long_break_interval` must be greater than 0, using default");
+        config.long_break_interval = Config::default().long_break_interval;
+    }
+    config
+}
+
+fn write(path: &PathBuf, config: &Config) {
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(contents) = toml::to_string_pretty(config) {
+        let _ = std::fs::write(path, contents);
+    }
+}
+
+/// Path to `config.toml` under the platform-appropriate config directory.
+pub fn config_path() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "pomo").map(|dirs| dirs.config_dir().join("config.toml"))
+}
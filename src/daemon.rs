@@ -0,0 +1,120 @@
+/// Daemon mode: a Unix-socket control channel so `pomo pause`/`resume`/
+/// `skip`/`stop`/`status` from another shell can steer a `pomo daemon`
+/// running the actual timer loop.
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+
+use crate::timer::{SharedTimer, TimerStatus};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum DaemonCommand {
+    Pause,
+    Resume,
+    Skip,
+    Stop,
+    Status,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DaemonReply {
+    pub message: String,
+}
+
+/// `$XDG_RUNTIME_DIR/pomo.sock`, falling back to `/tmp` when unset.
+pub fn socket_path() -> PathBuf {
+    let runtime_dir = std::env::var("XDG_RUNTIME_DIR").unwrap_or_else(|_| "/tmp".to_string());
+    PathBuf::from(runtime_dir).join("pomo.sock")
+}
+
+/// Binds the control socket and spawns a background thread that applies
+/// incoming commands to `shared` for as long as the daemon runs.
+pub fn spawn_control_listener(shared: Arc<SharedTimer>) {
+    let path = socket_path();
+    let _ = std::fs::remove_file(&path);
+
+    let listener = match UnixListener::bind(&path) {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("Warning: could not bind control socket at {}: {}", path.display(), err);
+            return;
+        }
+    };
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else { continue };
+            handle_connection(stream, &shared);
+        }
+    });
+}
+
+fn handle_connection(mut stream: UnixStream, shared: &Arc<SharedTimer>) {
+    let mut reader = BufReader::new(match stream.try_clone() {
+        Ok(clone) => clone,
+        Err(_) => return,
+    });
+    let mut line = String::new();
+    if reader.read_line(&mut line).unwrap_or(0) == 0 {
+        return;
+    }
+
+    let reply = match serde_json::from_str::<DaemonCommand>(line.trim()) {
+        Ok(command) => apply(command, shared),
+        Err(_) => DaemonReply { message: "error: invalid command".to_string() },
+    };
+
+    let _ = writeln!(stream, "{}", serde_json::to_string(&reply).unwrap_or_default());
+}
+
+fn apply(command: DaemonCommand, shared: &Arc<SharedTimer>) -> DaemonReply {
+    let message = match command {
+        DaemonCommand::Pause => {
+            shared.pause();
+            "paused".to_string()
+        }
+        DaemonCommand::Resume => {
+            shared.resume();
+            "resumed".to_string()
+        }
+        DaemonCommand::Skip => {
+            shared.skip();
+            "skip requested".to_string()
+        }
+        DaemonCommand::Stop => {
+            shared.stop();
+            "stop requested".to_string()
+        }
+        DaemonCommand::Status => format_status(&shared.status()),
+    };
+    DaemonReply { message }
+}
+
+fn format_status(status: &TimerStatus) -> String {
+    let minutes = status.remaining_secs / 60;
+    let seconds = status.remaining_secs % 60;
+    let paused = if status.paused { " (paused)" } else { "" };
+    format!(
+        "{:?} - cycle {}/{} - {:02}:{:02} remaining{}",
+        status.state, status.cycle, status.total_cycles, minutes, seconds, paused
+    )
+}
+
+/// Connects to a running daemon, sends `command`, and returns its reply.
+pub fn send_command(command: DaemonCommand) -> Result<DaemonReply, String> {
+    let path = socket_path();
+    let mut stream = UnixStream::connect(&path)
+        .map_err(|err| format!("could not connect to daemon at {}: {}", path.display(), err))?;
+
+    let payload = serde_json::to_string(&command).map_err(|err| err.to_string())?;
+    writeln!(stream, "{}", payload).map_err(|err| err.to_string())?;
+
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|err| err.to_string())?;
+    serde_json::from_str(line.trim()).map_err(|err| err.to_string())
+}
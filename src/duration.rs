@@ -0,0 +1,141 @@
+/// Shared duration grammar: used both for the `--duration` CLI flag
+/// (`main::parse_duration`) and for (de)serializing TOML duration fields
+/// (`config::duration_str`). Keeping one parser means the two can't
+/// silently drift apart on what's valid input.
+use std::time::Duration;
+
+/// Parses strings like `25m`, `1h30m`, `200ms`, or a bare number (treated
+/// as minutes, for backward compatibility with the original `--duration`
+/// flag).
+pub fn parse(input: &str) -> Result<Duration, String> {
+    let input = input.trim().to_lowercase();
+
+    if let Ok(minutes) = input.parse::<u64>() {
+        return Ok(Duration::from_secs(minutes * 60));
+    }
+
+    let mut total_millis: u128 = 0;
+    let mut current_number = String::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch.is_ascii_digit() {
+            current_number.push(ch);
+        } else if ch == 'h' || ch == 's' || (ch == 'm' && chars.peek() != Some(&'s')) {
+            if current_number.is_empty() {
+                return Err(invalid_format_error());
+            }
+
+            let number: u128 = current_number.parse()
+                .map_err(|_| "Invalid number in duration".to_string())?;
+
+            match ch {
+                'h' => total_millis += number * 3_600_000,
+                'm' => total_millis += number * 60_000,
+                's' => total_millis += number * 1_000,
+                _ => unreachable!(),
+            }
+
+            current_number.clear();
+        } else if ch == 'm' && chars.peek() == Some(&'s') {
+            chars.next();
+
+            if current_number.is_empty() {
+                return Err(invalid_format_error());
+            }
+
+            let number: u128 = current_number.parse()
+                .map_err(|_| "Invalid number in duration".to_string())?;
+            total_millis += number;
+            current_number.clear();
+        } else if !ch.is_whitespace() {
+            return Err(format!("Invalid character in duration. {}", invalid_format_error()));
+        }
+    }
+
+    if !current_number.is_empty() {
+        return Err("Duration must end with 'h', 'm', 's' or 'ms'".to_string());
+    }
+
+    Ok(Duration::from_millis(total_millis as u64))
+}
+
+fn invalid_format_error() -> String {
+    "Invalid duration format. Use formats like: 1h, 25m, 30s, 200ms, 1h30m".to_string()
+}
+
+/// Formats a `Duration` compactly as e.g. `1h30m`, `25m`, or `200ms`,
+/// combining whichever units are non-zero (falling back to `0ms` for a
+/// zero duration).
+pub fn format_compact(duration: Duration) -> String {
+    let mut millis = duration.as_millis();
+    let hours = millis / 3_600_000;
+    millis %= 3_600_000;
+    let minutes = millis / 60_000;
+    millis %= 60_000;
+    let secs = millis / 1_000;
+    millis %= 1_000;
+
+    let mut out = String::new();
+    if hours > 0 {
+        out.push_str(&format!("{}h", hours));
+    }
+    if minutes > 0 {
+        out.push_str(&format!("{}m", minutes));
+    }
+    if secs > 0 {
+        out.push_str(&format!("{}s", secs));
+    }
+    if millis > 0 || out.is_empty() {
+        out.push_str(&format!("{}ms", millis));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bare_number_as_minutes() {
+        assert_eq!(parse("25").unwrap(), Duration::from_secs(25 * 60));
+    }
+
+    #[test]
+    fn parses_combined_units() {
+        assert_eq!(parse("1h30m").unwrap(), Duration::from_secs(90 * 60));
+    }
+
+    #[test]
+    fn parses_sub_second_precision() {
+        assert_eq!(parse("200ms").unwrap(), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn parses_seconds_and_milliseconds_together() {
+        assert_eq!(parse("1s500ms").unwrap(), Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn rejects_unknown_unit() {
+        assert!(parse("30x").is_err());
+    }
+
+    #[test]
+    fn rejects_trailing_number_without_unit() {
+        assert!(parse("1h30").is_err());
+    }
+
+    #[test]
+    fn round_trips_through_format_compact() {
+        for input in ["1h30m", "25m", "30s", "200ms", "1h"] {
+            let duration = parse(input).unwrap();
+            assert_eq!(parse(&format_compact(duration)).unwrap(), duration);
+        }
+    }
+
+    #[test]
+    fn formats_zero_as_zero_ms() {
+        assert_eq!(format_compact(Duration::ZERO), "0ms");
+    }
+}
@@ -0,0 +1,206 @@
+/// Session history: an append-only JSON-lines log of completed work
+/// sessions under the config directory, plus the aggregates behind
+/// `pomo stats`.
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+
+use crate::timer::TimerStatus;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SessionRecord {
+    pub started_at_unix: u64,
+    pub configured_duration_secs: u64,
+    pub elapsed_secs: u64,
+    pub completed: bool,
+    pub cycle: usize,
+}
+
+/// Builds the record for a work phase from its `SharedTimer` snapshot:
+/// elapsed time is back-computed from how much was left when it ended.
+pub fn record_from_status(status: &TimerStatus, completed: bool) -> SessionRecord {
+    SessionRecord {
+        started_at_unix: status.started_at_unix,
+        configured_duration_secs: status.configured_secs,
+        elapsed_secs: status.configured_secs.saturating_sub(status.remaining_secs),
+        completed,
+        cycle: status.cycle,
+    }
+}
+
+pub fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Path to `history.jsonl` under the platform-appropriate config directory.
+pub fn history_path() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "pomo").map(|dirs| dirs.config_dir().join("history.jsonl"))
+}
+
+/// Appends one record as a line of JSON. Failures are swallowed with a
+/// warning: a missing history entry shouldn't interrupt a running timer.
+pub fn append(record: &SessionRecord) {
+    let Some(path) = history_path() else {
+        eprintln!("Warning: could not determine config directory, not recording session");
+        return;
+    };
+
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+
+    let Ok(line) = serde_json::to_string(record) else {
+        return;
+    };
+
+    match OpenOptions::new().create(true).append(true).open(&path) {
+        Ok(mut file) => {
+            let _ = writeln!(file, "{}", line);
+        }
+        Err(err) => eprintln!("Warning: could not write to history log: {}", err),
+    }
+}
+
+/// Reads every record in the log, skipping any line that fails to parse
+/// (e.g. if the log was truncated mid-write).
+pub fn read_all() -> Vec<SessionRecord> {
+    let Some(path) = history_path() else {
+        return Vec::new();
+    };
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    pub completed_today: usize,
+    pub completed_this_week: usize,
+    pub total_focused_minutes: u64,
+    pub completion_rate: f64,
+}
+
+/// Aggregates records into the summary shown by `pomo stats`. "Today" and
+/// "this week" are UTC calendar boundaries (midnight, and the most recent
+/// Monday midnight), not rolling 24h/7-day windows, since the log only
+/// stores Unix timestamps and the repo doesn't carry a timezone dependency.
+pub fn compute(records: &[SessionRecord]) -> Stats {
+    let now = now_unix();
+    let today_start = day_start(now);
+    let week_start = today_start.saturating_sub(days_since_monday(now) * 24 * 60 * 60);
+
+    let completed_today = records
+        .iter()
+        .filter(|r| r.completed && r.started_at_unix >= today_start)
+        .count();
+    let completed_this_week = records
+        .iter()
+        .filter(|r| r.completed && r.started_at_unix >= week_start)
+        .count();
+    let total_focused_secs: u64 = records.iter().filter(|r| r.completed).map(|r| r.elapsed_secs).sum();
+    let total_focused_minutes = total_focused_secs / 60;
+    let completion_rate = if records.is_empty() {
+        0.0
+    } else {
+        records.iter().filter(|r| r.completed).count() as f64 / records.len() as f64 * 100.0
+    };
+
+    Stats {
+        completed_today,
+        completed_this_week,
+        total_focused_minutes,
+        completion_rate,
+    }
+}
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// UTC midnight for the day containing `unix_secs`.
+fn day_start(unix_secs: u64) -> u64 {
+    (unix_secs / SECS_PER_DAY) * SECS_PER_DAY
+}
+
+/// Days since the most recent UTC Monday midnight (0 if `unix_secs` falls
+/// on a Monday). The Unix epoch (1970-01-01) was a Thursday, i.e. weekday
+/// index 3 in a Monday-is-0 scheme.
+fn days_since_monday(unix_secs: u64) -> u64 {
+    let days_since_epoch = unix_secs / SECS_PER_DAY;
+    (days_since_epoch + 3) % 7
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(started_at_unix: u64, elapsed_secs: u64, completed: bool) -> SessionRecord {
+        SessionRecord {
+            started_at_unix,
+            configured_duration_secs: elapsed_secs,
+            elapsed_secs,
+            completed,
+            cycle: 1,
+        }
+    }
+
+    #[test]
+    fn sums_seconds_before_converting_to_minutes() {
+        let now = now_unix();
+        let records = vec![record(now, 90, true), record(now, 90, true)];
+        // Flooring each record to minutes first would give 1 + 1 = 2.
+        assert_eq!(compute(&records).total_focused_minutes, 3);
+    }
+
+    #[test]
+    fn excludes_incomplete_sessions_from_focused_time() {
+        let now = now_unix();
+        let records = vec![record(now, 120, true), record(now, 120, false)];
+        assert_eq!(compute(&records).total_focused_minutes, 2);
+    }
+
+    #[test]
+    fn today_excludes_sessions_from_before_the_calendar_day_started() {
+        let now = now_unix();
+        let records = vec![record(today_start_for_test(now), 60, true)];
+        assert_eq!(compute(&records).completed_today, 1);
+
+        let yesterday = vec![record(today_start_for_test(now).saturating_sub(1), 60, true)];
+        assert_eq!(compute(&yesterday).completed_today, 0);
+    }
+
+    #[test]
+    fn week_includes_today_but_excludes_more_than_a_week_ago() {
+        let now = now_unix();
+        let eight_days_ago = now.saturating_sub(8 * SECS_PER_DAY);
+        let records = vec![record(now, 60, true), record(eight_days_ago, 60, true)];
+        let stats = compute(&records);
+        assert_eq!(stats.completed_this_week, 1);
+    }
+
+    #[test]
+    fn completion_rate_counts_completed_over_total() {
+        let now = now_unix();
+        let records = vec![record(now, 60, true), record(now, 60, true), record(now, 60, false)];
+        assert!((compute(&records).completion_rate - (200.0 / 3.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn completion_rate_is_zero_for_no_records() {
+        assert_eq!(compute(&[]).completion_rate, 0.0);
+    }
+
+    fn today_start_for_test(now: u64) -> u64 {
+        day_start(now)
+    }
+}
@@ -1,51 +1,86 @@
-use std::thread;
-use std::time::{Duration, Instant};
+mod audio;
+mod config;
+mod daemon;
+mod duration;
+mod history;
+mod notify;
+mod timer;
+
 use std::env;
+use std::io::IsTerminal;
 use std::process::Command;
-use std::fs;
-use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
-use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-struct Config {
-    hooks: Hooks,
+use config::Config;
+use daemon::DaemonCommand;
+use timer::{run_timer, SharedTimer, TickOutcome, TimerState};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    match args.get(1).map(String::as_str) {
+        Some("start") => run_foreground(&args, false),
+        Some("daemon") => run_foreground(&args, true),
+        Some("pause") => send_simple_command(DaemonCommand::Pause),
+        Some("resume") => send_simple_command(DaemonCommand::Resume),
+        Some("skip") => send_simple_command(DaemonCommand::Skip),
+        Some("stop") => send_simple_command(DaemonCommand::Stop),
+        Some("status") => send_simple_command(DaemonCommand::Status),
+        Some("stats") => print_stats(),
+        _ => print_usage(),
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-struct Hooks {
-    work_start: Option<String>,
-    work_end: Option<String>,
-    break_start: Option<String>,
-    break_end: Option<String>,
+fn print_usage() {
+    println!("Usage: pomo <start|daemon|pause|resume|skip|stop|status|stats> [--duration <time>] [--no-music] [--notify|--no-notify]");
+    println!("  <time> format: 1h, 25m, 30s, 1h30m");
+    println!("  daemon also listens on a control socket for pause/resume/skip/stop/status");
+    println!("  while running: space to pause/resume, s to skip, q to quit (foreground terminal sessions only)");
+    println!("  stats summarizes completed sessions from the history log");
 }
 
-#[derive(Debug, Clone)]
-enum TimerState {
-    Work,
-    Break,
-    Idle,
+fn print_stats() {
+    let records = history::read_all();
+    let stats = history::compute(&records);
+    println!("📊 Pomodoro stats");
+    println!("  Completed today:     {}", stats.completed_today);
+    println!("  Completed this week: {}", stats.completed_this_week);
+    println!("  Total focused time:  {} minutes", stats.total_focused_minutes);
+    println!("  Completion rate:     {:.0}%", stats.completion_rate);
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    if args.len() < 2 || args[1] != "start" {
-        println!("Usage: pomo start [--duration <time>] [--no-music]");
-        println!("  <time> format: 25m, 30s, 1m30s");
-        return;
+fn send_simple_command(command: DaemonCommand) {
+    match daemon::send_command(command) {
+        Ok(reply) => println!("{}", reply.message),
+        Err(err) => println!("Error: {}", err),
     }
-    
+}
+
+/// Runs the timer loop in this process. When `as_daemon` is set, also binds
+/// the control socket so `pomo pause`/`resume`/`skip`/`stop`/`status` from
+/// another shell can steer this run.
+fn run_foreground(args: &[String], as_daemon: bool) {
     let no_music = args.contains(&"--no-music".to_string());
-    
-    // Parse duration flag
-    let mut duration_seconds = 25 * 60; // default 25 minutes in seconds
+
+    let mut config = config::load();
+
+    // --notify/--no-notify override the configured toggle for this run
+    if args.contains(&"--notify".to_string()) {
+        config.notify = true;
+    }
+    if args.contains(&"--no-notify".to_string()) {
+        config.notify = false;
+    }
+
+    // Parse duration flag (overrides the configured work duration for this run)
     if let Some(duration_index) = args.iter().position(|x| x == "--duration") {
         if duration_index + 1 < args.len() {
             match parse_duration(&args[duration_index + 1]) {
                 Ok(seconds) => {
                     if seconds > 0 {
-                        duration_seconds = seconds;
+                        config.work_duration = Duration::from_secs(seconds);
                     } else {
                         println!("Error: Duration must be greater than 0");
                         return;
@@ -62,18 +97,24 @@ fn main() {
         }
     }
 
-    let config = Arc::new(load_config());
-    let timer_state = Arc::new(Mutex::new(TimerState::Idle));
-    
+    let config = Arc::new(config);
+    let shared = SharedTimer::new(config.cycles);
+
+    if as_daemon {
+        daemon::spawn_control_listener(Arc::clone(&shared));
+        println!("🍅 pomo daemon listening on {}", daemon::socket_path().display());
+    }
+
     // Setup Ctrl-C handler
     let config_clone = Arc::clone(&config);
-    let state_clone = Arc::clone(&timer_state);
+    let shared_clone = Arc::clone(&shared);
     let no_music_clone = no_music;
     ctrlc::set_handler(move || {
-        let state = state_clone.lock().unwrap();
         println!("\n🛑 Interrupted!");
-        match *state {
+        let status = shared_clone.status();
+        match status.state {
             TimerState::Work => {
+                history::append(&history::record_from_status(&status, false));
                 if !no_music_clone {
                     execute_hook(&config_clone.hooks.work_end);
                 }
@@ -90,145 +131,120 @@ fn main() {
         std::process::exit(0);
     }).expect("Error setting Ctrl-C handler");
 
-    run_pomodoro(config, timer_state, no_music, duration_seconds);
-}
+    // Keyboard controls only make sense for a foreground session attached
+    // to a real terminal; a backgrounded daemon (or redirected stdin) must
+    // not try to grab raw mode (see `timer::run_timer`).
+    let interactive = !as_daemon && std::io::stdin().is_terminal();
 
-fn run_pomodoro(config: Arc<Config>, timer_state: Arc<Mutex<TimerState>>, no_music: bool, duration_seconds: u64) {
-    // Work timer
-    let duration_display = format_duration(duration_seconds);
-    println!("🍅 Starting {} Pomodoro work session...", duration_display);
-    *timer_state.lock().unwrap() = TimerState::Work;
-    if !no_music {
-        execute_hook(&config.hooks.work_start);
-    }
-    run_timer(duration_seconds);
-    if !no_music {
-        execute_hook(&config.hooks.work_end);
-        thread::sleep(Duration::from_millis(200)); // Allow time for hook to complete before system beep
-    }
-    if !no_music {
-        system_beep();
-    }
-    println!("🍅 Work session complete! Time for a break.");
-    
-    // 5-minute break timer
-    println!("☕ Starting 5-minute break...");
-    *timer_state.lock().unwrap() = TimerState::Break;
-    if !no_music {
-        execute_hook(&config.hooks.break_start);
-    }
-    run_timer(5 * 60);
-    if !no_music {
-        execute_hook(&config.hooks.break_end);
-        thread::sleep(Duration::from_millis(200)); // Allow time for hook to complete before system beep
-    }
-    if !no_music {
-        system_beep();
-    }
-    *timer_state.lock().unwrap() = TimerState::Idle;
-    println!("☕ Break complete! Ready for another session?");
+    run_pomodoro(config, shared, no_music, interactive);
 }
 
-fn run_timer(duration_seconds: u64) {
-    let start_time = Instant::now();
-    let total_duration = Duration::from_secs(duration_seconds);
-    
-    while start_time.elapsed() < total_duration {
-        let elapsed = start_time.elapsed();
-        let remaining = total_duration - elapsed;
-        
-        // Time formatting
-        let minutes = remaining.as_secs() / 60;
-        let seconds = remaining.as_secs() % 60;
-        
-        // Progress calculation
-        let progress_ratio = elapsed.as_secs_f64() / total_duration.as_secs_f64();
-        let percentage = (progress_ratio * 100.0) as u8;
-        
-        // Progress bar generation
-        let bar_width = 20;
-        let filled_blocks = (progress_ratio * bar_width as f64) as usize;
-        let empty_blocks = bar_width - filled_blocks;
-        
-        let progress_bar = format!("{}{}",
-            "█".repeat(filled_blocks),
-            "░".repeat(empty_blocks)
+/// Runs the full Pomodoro cycle: `config.cycles` work blocks, each followed
+/// by a short break, except every `long_break_interval`th block which gets
+/// a long break instead (and the final block, which gets none).
+fn run_pomodoro(config: Arc<Config>, shared: Arc<SharedTimer>, no_music: bool, interactive: bool) {
+    for cycle in 1..=config.cycles {
+        println!(
+            "🍅 Starting cycle {}/{}: {} work session...",
+            cycle,
+            config.cycles,
+            format_duration(config.work_duration.as_secs())
         );
-        
-        // Combined display
-        print!("\r⏱️  {:02}:{:02} remaining [{}] {}%", 
-            minutes, seconds, progress_bar, percentage);
-        
-        std::io::Write::flush(&mut std::io::stdout()).unwrap();
-        
-        thread::sleep(Duration::from_millis(1000));
-    }
-    
-    println!("\r⏱️  00:00 - Time's up! [{}] 100%", "█".repeat(20));
-}
+        if config.notify {
+            notify::send(
+                "🍅 Work session started",
+                &format!("Cycle {}/{} — {}", cycle, config.cycles, format_duration(config.work_duration.as_secs())),
+            );
+        }
+        shared.begin_phase(TimerState::Work, cycle, history::now_unix(), config.work_duration.as_secs());
+        if !no_music {
+            execute_hook(&config.hooks.work_start);
+        }
+        let outcome = run_timer(config.work_duration.as_secs(), &shared, interactive);
+        history::append(&history::record_from_status(&shared.status(), matches!(outcome, TickOutcome::Elapsed)));
+        match outcome {
+            TickOutcome::Stopped => return finish_stopped(&config, &shared, no_music),
+            TickOutcome::Skipped => println!("⏭️  Work session skipped."),
+            TickOutcome::Elapsed => {}
+        }
+        if !no_music {
+            execute_hook(&config.hooks.work_end);
+            thread::sleep(Duration::from_millis(200)); // Allow time for hook to complete before the alert tone
+            audio::play_work_end(&config.audio);
+        }
+        println!("🍅 Work session complete!");
+        if config.notify {
+            notify::send("🍅 Work session complete", &remaining_cycles_message(cycle, config.cycles));
+        }
 
-fn load_config() -> Config {
-    let config_path = get_config_path();
-    
-    if config_path.exists() {
-        let config_content = fs::read_to_string(&config_path).unwrap_or_else(|_| {
-            eprintln!("Warning: Could not read config file, using defaults");
-            create_default_config(&config_path)
-        });
-        
-        serde_json::from_str(&config_content).unwrap_or_else(|_| {
-            eprintln!("Warning: Invalid config format, using defaults");
-            let default_config = Config {
-                hooks: Hooks {
-                    work_start: None,
-                    work_end: None,
-                    break_start: None,
-                    break_end: None,
-                }
-            };
-            let _ = fs::write(&config_path, serde_json::to_string_pretty(&default_config).unwrap());
-            default_config
-        })
-    } else {
-        let default_config = Config {
-            hooks: Hooks {
-                work_start: None,
-                work_end: None,
-                break_start: None,
-                break_end: None,
-            }
-        };
-        
-        if let Some(parent) = config_path.parent() {
-            let _ = fs::create_dir_all(parent);
+        if cycle == config.cycles {
+            break;
+        }
+
+        let is_long_break = cycle % config.long_break_interval == 0;
+        let break_duration = if is_long_break { config.long_break } else { config.short_break };
+        println!(
+            "☕ Starting {} break: {}...",
+            if is_long_break { "long" } else { "short" },
+            format_duration(break_duration.as_secs())
+        );
+        if config.notify {
+            notify::send(
+                &format!("☕ {} break started", if is_long_break { "Long" } else { "Short" }),
+                &format_duration(break_duration.as_secs()),
+            );
+        }
+        shared.begin_phase(TimerState::Break, cycle, history::now_unix(), break_duration.as_secs());
+        if !no_music {
+            execute_hook(&config.hooks.break_start);
+        }
+        match run_timer(break_duration.as_secs(), &shared, interactive) {
+            TickOutcome::Stopped => return finish_stopped(&config, &shared, no_music),
+            TickOutcome::Skipped => println!("⏭️  Break skipped."),
+            TickOutcome::Elapsed => {}
+        }
+        if !no_music {
+            execute_hook(&config.hooks.break_end);
+            thread::sleep(Duration::from_millis(200)); // Allow time for hook to complete before the alert tone
+            audio::play_break_end(&config.audio);
+        }
+        println!("☕ Break complete!");
+        if config.notify {
+            notify::send("☕ Break complete", &format!("Cycle {} of {} next", cycle + 1, config.cycles));
         }
-        
-        let config_json = create_default_config(&config_path);
-        serde_json::from_str(&config_json).unwrap_or(default_config)
+    }
+
+    shared.begin_phase(TimerState::Idle, config.cycles, 0, 0);
+    println!("🎉 All {} cycles complete! Great work.", config.cycles);
+    if config.notify {
+        notify::send("🎉 Pomodoro complete", &format!("All {} cycles finished. Great work!", config.cycles));
     }
 }
 
-fn get_config_path() -> PathBuf {
-    if let Some(home) = env::var_os("HOME") {
-        PathBuf::from(home).join(".config").join("pomo").join("config.json")
+fn remaining_cycles_message(cycle: usize, total_cycles: usize) -> String {
+    if cycle == total_cycles {
+        "All cycles complete!".to_string()
     } else {
-        PathBuf::from("pomo-config.json")
+        format!("{} cycle{} remaining", total_cycles - cycle, if total_cycles - cycle == 1 { "" } else { "s" })
     }
 }
 
-fn create_default_config(config_path: &PathBuf) -> String {
-    let default_config = Config {
-        hooks: Hooks {
-            work_start: Some("# afplay ~/music/focus.mp3 &".to_string()),
-            work_end: Some("# pkill afplay".to_string()),
-            break_start: Some("# afplay ~/music/break.mp3 &".to_string()),
-            break_end: Some("# pkill afplay".to_string()),
-        }
+/// Runs the matching `*_end` hook for whatever phase was active, then marks
+/// the timer idle. Used when a phase is stopped by the `stop` control
+/// command or the `q` key; the Ctrl-C handler does its own hook dispatch.
+fn finish_stopped(config: &Arc<Config>, shared: &Arc<SharedTimer>, no_music: bool) {
+    let hook = match shared.current_state() {
+        TimerState::Work => Some(&config.hooks.work_end),
+        TimerState::Break => Some(&config.hooks.break_end),
+        TimerState::Idle => None,
     };
-    
-    let config_json = serde_json::to_string_pretty(&default_config).unwrap();
-    let _ = fs::write(config_path, &config_json);
-    config_json
+    if let Some(hook) = hook {
+        if !no_music {
+            execute_hook(hook);
+        }
+    }
+    shared.begin_phase(TimerState::Idle, 0, 0, 0);
+    println!("🛑 Stopped.");
 }
 
 fn execute_hook(hook: &Option<String>) {
@@ -248,49 +264,16 @@ fn execute_hook(hook: &Option<String>) {
     }
 }
 
-fn parse_duration(input: &str) -> Result<u64, String> {
-    let input = input.trim().to_lowercase();
-    
-    // If it's just a number, treat as minutes for backward compatibility
-    if let Ok(minutes) = input.parse::<u64>() {
-        return Ok(minutes * 60);
-    }
-    
-    let mut total_seconds = 0u64;
-    let mut current_number = String::new();
-    
-    for ch in input.chars() {
-        if ch.is_ascii_digit() {
-            current_number.push(ch);
-        } else if ch == 'm' || ch == 's' {
-            if current_number.is_empty() {
-                return Err("Invalid duration format. Use formats like: 25m, 30s, 1m30s".to_string());
-            }
-            
-            let number: u64 = current_number.parse()
-                .map_err(|_| "Invalid number in duration".to_string())?;
-            
-            match ch {
-                'm' => total_seconds += number * 60,
-                's' => total_seconds += number,
-                _ => unreachable!(),
-            }
-            
-            current_number.clear();
-        } else if !ch.is_whitespace() {
-            return Err("Invalid character in duration. Use formats like: 25m, 30s, 1m30s".to_string());
-        }
-    }
-    
-    if !current_number.is_empty() {
-        return Err("Duration must end with 'm' (minutes) or 's' (seconds)".to_string());
-    }
-    
-    if total_seconds == 0 {
+/// Parses the `--duration` CLI flag using the shared `duration` grammar,
+/// truncated to whole seconds (the CLI has always worked at that
+/// granularity; sub-second precision only matters for `audio.tone_duration`
+/// in `config.rs`).
+pub(crate) fn parse_duration(input: &str) -> Result<u64, String> {
+    let parsed = duration::parse(input)?;
+    if parsed.is_zero() {
         return Err("Duration must be greater than 0".to_string());
     }
-    
-    Ok(total_seconds)
+    Ok(parsed.as_secs())
 }
 
 fn format_duration(seconds: u64) -> String {
@@ -308,73 +291,3 @@ fn format_duration(seconds: u64) -> String {
     }
 }
 
-fn system_beep() {
-    let mut sound_played = false;
-    
-    if cfg!(target_os = "macos") {
-        // Try different macOS system sounds
-        let sounds = [
-            "/System/Library/Sounds/Glass.aiff",
-            "/System/Library/Sounds/Ping.aiff",
-            "/System/Library/Sounds/Pop.aiff",
-            "/System/Library/Sounds/Purr.aiff"
-        ];
-        
-        for sound_path in &sounds {
-            if let Ok(mut child) = Command::new("afplay")
-                .arg(sound_path)
-                .spawn() {
-                if child.wait().is_ok() {
-                    sound_played = true;
-                    break;
-                }
-            }
-        }
-        
-        // Fallback to say command for macOS
-        if !sound_played {
-            if let Ok(mut child) = Command::new("say")
-                .arg("Time up")
-                .spawn() {
-                let _ = child.wait();
-                sound_played = true;
-            }
-        }
-    } else if cfg!(target_os = "linux") {
-        let sounds = [
-            "/usr/share/sounds/alsa/Front_Left.wav",
-            "/usr/share/sounds/sound-icons/bell.wav",
-            "/usr/share/sounds/gnome/default/alerts/glass.ogg"
-        ];
-        
-        for sound_path in &sounds {
-            if let Ok(mut child) = Command::new("paplay")
-                .arg(sound_path)
-                .spawn() {
-                if child.wait().is_ok() {
-                    sound_played = true;
-                    break;
-                }
-            }
-        }
-        
-        if !sound_played {
-            for sound_path in &sounds {
-                if let Ok(mut child) = Command::new("aplay")
-                    .arg(sound_path)
-                    .spawn() {
-                    if child.wait().is_ok() {
-                        sound_played = true;
-                        break;
-                    }
-                }
-            }
-        }
-    }
-    
-    // Always print the bell character as fallback
-    if !sound_played {
-        print!("\x07");
-        std::io::Write::flush(&mut std::io::stdout()).unwrap();
-    }
-}
@@ -0,0 +1,11 @@
+/// Desktop notifications at each Pomodoro transition, via `notify-rust`.
+///
+/// The in-terminal progress bar only reaches a user who's watching the
+/// terminal; a native notification reaches one who switched windows.
+use notify_rust::Notification;
+
+pub fn send(summary: &str, body: &str) {
+    if let Err(err) = Notification::new().summary(summary).body(body).show() {
+        eprintln!("Warning: could not send desktop notification: {}", err);
+    }
+}
@@ -1,12 +1,250 @@
-/// Timer module for handling Pomodoro sessions
+/// Timer module: the Pomodoro phase machine and the shared control state
+/// that lets a daemon's control socket (or the interactive keyboard
+/// controls below) pause, resume, skip, or stop a running timer.
+use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-pub fn start_timer(duration: Duration) {
-    thread::sleep(duration);
-    println!("Time's up! \x07"); // \x07 is ASCII bell character
+use crossterm::event::{self, Event, KeyCode};
+use crossterm::terminal;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerState {
+    Work,
+    Break,
+    Idle,
+}
+
+/// Snapshot returned to `pomo status` and to the session history log.
+#[derive(Debug, Clone)]
+pub struct TimerStatus {
+    pub state: TimerState,
+    pub remaining_secs: u64,
+    pub cycle: usize,
+    pub total_cycles: usize,
+    pub paused: bool,
+    pub started_at_unix: u64,
+    pub configured_secs: u64,
+}
+
+struct Inner {
+    state: TimerState,
+    cycle: usize,
+    total_cycles: usize,
+    remaining_secs: u64,
+    paused_at: Option<Instant>,
+    total_paused: Duration,
+    skip_requested: bool,
+    stop_requested: bool,
+    started_at_unix: u64,
+    configured_secs: u64,
+}
+
+/// Shared control surface for a running timer: the daemon's control loop
+/// (see `daemon::handle_connection`) mutates this from socket commands, and
+/// `run_timer` reads it every tick.
+pub struct SharedTimer {
+    inner: Mutex<Inner>,
+}
+
+pub enum TickOutcome {
+    Elapsed,
+    Skipped,
+    Stopped,
+}
+
+impl SharedTimer {
+    pub fn new(total_cycles: usize) -> Arc<Self> {
+        Arc::new(Self {
+            inner: Mutex::new(Inner {
+                state: TimerState::Idle,
+                cycle: 0,
+                total_cycles,
+                remaining_secs: 0,
+                paused_at: None,
+                total_paused: Duration::ZERO,
+                skip_requested: false,
+                stop_requested: false,
+                started_at_unix: 0,
+                configured_secs: 0,
+            }),
+        })
+    }
+
+    /// Marks the start of a new phase, clearing any pause/skip left over
+    /// from the previous one. `started_at_unix` and `configured_secs`
+    /// are recorded so an interrupted work phase can still be logged to
+    /// the session history from the Ctrl-C handler.
+    pub fn begin_phase(&self, state: TimerState, cycle: usize, started_at_unix: u64, configured_secs: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.state = state;
+        inner.cycle = cycle;
+        inner.paused_at = None;
+        inner.total_paused = Duration::ZERO;
+        inner.skip_requested = false;
+        inner.started_at_unix = started_at_unix;
+        inner.configured_secs = configured_secs;
+        inner.remaining_secs = configured_secs;
+    }
+
+    pub fn current_state(&self) -> TimerState {
+        self.inner.lock().unwrap().state
+    }
+
+    pub fn pause(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.paused_at.is_none() && inner.state != TimerState::Idle {
+            inner.paused_at = Some(Instant::now());
+        }
+    }
+
+    pub fn resume(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(paused_at) = inner.paused_at.take() {
+            inner.total_paused += paused_at.elapsed();
+        }
+    }
+
+    pub fn skip(&self) {
+        self.inner.lock().unwrap().skip_requested = true;
+    }
+
+    pub fn stop(&self) {
+        self.inner.lock().unwrap().stop_requested = true;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.inner.lock().unwrap().paused_at.is_some()
+    }
+
+    pub fn status(&self) -> TimerStatus {
+        let inner = self.inner.lock().unwrap();
+        TimerStatus {
+            state: inner.state,
+            remaining_secs: inner.remaining_secs,
+            cycle: inner.cycle,
+            total_cycles: inner.total_cycles,
+            paused: inner.paused_at.is_some(),
+            started_at_unix: inner.started_at_unix,
+            configured_secs: inner.configured_secs,
+        }
+    }
 }
 
-pub fn duration_from_minutes(minutes: u64) -> Duration {
-    Duration::from_secs(minutes * 60)
+/// Puts the terminal into raw mode for the lifetime of the guard so single
+/// keypresses (space/s/q) can be read without waiting for Enter, restoring
+/// the terminal on drop even if `run_timer` returns early.
+struct RawModeGuard;
+
+impl RawModeGuard {
+    fn enable() -> Option<Self> {
+        terminal::enable_raw_mode().ok()?;
+        Some(Self)
+    }
+}
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Reads a pending keypress (if any) without blocking the render loop and
+/// applies it to `shared`: space toggles pause/resume, `s` skips to the
+/// next phase, `q` stops (the caller then runs the matching `*_end` hook).
+fn handle_keypress(shared: &Arc<SharedTimer>) {
+    if let Ok(Event::Key(key_event)) = event::read() {
+        match key_event.code {
+            KeyCode::Char(' ') => {
+                if shared.is_paused() {
+                    shared.resume();
+                } else {
+                    shared.pause();
+                }
+            }
+            KeyCode::Char('s') | KeyCode::Char('S') => shared.skip(),
+            KeyCode::Char('q') | KeyCode::Char('Q') => shared.stop(),
+            _ => {}
+        }
+    }
+}
+
+/// Runs a single phase's countdown, ticking every ~250ms so pause/skip/stop
+/// requests made through `shared` (from the control socket or the keyboard)
+/// take effect quickly. While paused, the elapsed time accumulated so far
+/// is frozen against `Instant`, so resuming picks up exactly where it left
+/// off.
+///
+/// `interactive` gates the keyboard controls: raw mode is only enabled (and
+/// stdin only polled) when the caller is a foreground session attached to a
+/// real terminal. A backgrounded `pomo daemon` — or `pomo start` with stdin
+/// redirected — just sleeps each tick instead, since enabling raw mode from
+/// a background process group would raise `SIGTTOU` and stop the process,
+/// and raw mode also masks `Ctrl-C` (`ISIG`), which would otherwise make the
+/// process's own interrupt handling unreachable.
+pub fn run_timer(duration_seconds: u64, shared: &Arc<SharedTimer>, interactive: bool) -> TickOutcome {
+    let total_duration = Duration::from_secs(duration_seconds);
+    let start_time = Instant::now();
+    let raw_mode = if interactive { RawModeGuard::enable() } else { None };
+
+    loop {
+        // Waiting up to ~250ms for a keypress doubles as this tick's throttle.
+        if raw_mode.is_some() {
+            if event::poll(Duration::from_millis(250)).unwrap_or(false) {
+                handle_keypress(shared);
+            }
+        } else {
+            thread::sleep(Duration::from_millis(250));
+        }
+
+        let (paused, skip_requested, stop_requested, effective_elapsed) = {
+            let inner = shared.inner.lock().unwrap();
+            let paused_extra = inner.paused_at.map(|p| p.elapsed()).unwrap_or_default();
+            let total_paused = inner.total_paused + paused_extra;
+            let effective_elapsed = start_time.elapsed().saturating_sub(total_paused);
+            (inner.paused_at.is_some(), inner.skip_requested, inner.stop_requested, effective_elapsed)
+        };
+
+        if stop_requested {
+            println!();
+            return TickOutcome::Stopped;
+        }
+        if skip_requested {
+            println!();
+            return TickOutcome::Skipped;
+        }
+        if effective_elapsed >= total_duration {
+            break;
+        }
+
+        let remaining = total_duration - effective_elapsed;
+        shared.inner.lock().unwrap().remaining_secs = remaining.as_secs();
+        render(remaining, total_duration, paused);
+    }
+
+    shared.inner.lock().unwrap().remaining_secs = 0;
+    println!("\r⏱️  00:00 - Time's up! [{}] 100%", "█".repeat(20));
+    TickOutcome::Elapsed
+}
+
+fn render(remaining: Duration, total_duration: Duration, paused: bool) {
+    let minutes = remaining.as_secs() / 60;
+    let seconds = remaining.as_secs() % 60;
+
+    let elapsed = total_duration - remaining;
+    let progress_ratio = elapsed.as_secs_f64() / total_duration.as_secs_f64();
+    let percentage = (progress_ratio * 100.0) as u8;
+
+    let bar_width = 20;
+    let filled_blocks = (progress_ratio * bar_width as f64) as usize;
+    let empty_blocks = bar_width - filled_blocks;
+
+    let progress_bar = format!("{}{}", "█".repeat(filled_blocks), "░".repeat(empty_blocks));
+
+    let status = if paused { " [PAUSED]" } else { "" };
+    print!(
+        "\r⏱️  {:02}:{:02} remaining [{}] {}%{}",
+        minutes, seconds, progress_bar, percentage, status
+    );
+    std::io::Write::flush(&mut std::io::stdout()).unwrap();
 }